@@ -0,0 +1,122 @@
+use crate::ast::{BinOp, Expr};
+
+/// Target kind for a coercion attempt in [`coerce`].
+///
+/// Mirrors the value space `eval_binop`/`eval_unop` will eventually need
+/// to support. Coercions to a kind the AST doesn't carry a literal for
+/// yet (`Bytes`, `Timestamp`) are accepted by the API but fail closed
+/// today rather than guessing at a representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Integer,
+    Float,
+    Boolean,
+    Bytes,
+    Timestamp,
+}
+
+/// Attempt a principled coercion of `expr` to `target`.
+///
+/// Only conversions that cannot silently change meaning are attempted:
+/// an already-matching value passes through, `Number` always widens to
+/// `Float` (a lossless promotion), and `Number` coerces to `Boolean`
+/// (`0` / non-zero) only when `allow_int_to_bool` is set, so `1 == true`
+/// doesn't become true for every non-zero number by default. Returns
+/// `None` when no safe conversion exists, so the caller can fall back to
+/// a typed error instead of stalling the def.
+pub fn coerce(expr: &Expr, target: TargetKind, allow_int_to_bool: bool) -> Option<Expr> {
+    match (expr, target) {
+        (Expr::Number { val }, TargetKind::Integer) => Some(Expr::Number { val: *val }),
+        (Expr::Bool { val }, TargetKind::Boolean) => Some(Expr::Bool { val: *val }),
+        (Expr::Number { val }, TargetKind::Float) => Some(Expr::Float { val: *val as f64 }),
+        (Expr::Float { val }, TargetKind::Float) => Some(Expr::Float { val: *val }),
+        (Expr::Number { val }, TargetKind::Boolean) if allow_int_to_bool => {
+            Some(Expr::Bool { val: *val != 0 })
+        }
+        _ => None,
+    }
+}
+
+/// Compare two floats for the `Eq` operator using plain IEEE-754
+/// equality: `-0.0 == 0.0` is `true` and `NaN == NaN` is `false`, matching
+/// the semantics Rust's own `f64::eq` gives. The `clippy::float_cmp`
+/// allow marks this bare `==` as deliberate exact equality; the function
+/// gives that comparison a name and documents its semantics at the call
+/// site.
+#[allow(clippy::float_cmp)]
+pub fn float_eq(left: f64, right: f64) -> bool {
+    left == right
+}
+
+/// Build a typed error expression describing why `op` could not be
+/// evaluated over `left` and `right`, so the REPL can report the
+/// mismatch instead of the def silently stalling on an opaque `Binop`.
+pub fn coercion_error(op: &BinOp, left: &Expr, right: &Expr) -> Expr {
+    Expr::Error {
+        message: format!(
+            "cannot evaluate {:?} between {:?} and {:?}: no coercion to a common type",
+            op, left, right
+        ),
+    }
+}
+
+/// Build a typed error expression for an arithmetic op whose result is
+/// not finite (`NaN`/`±inf`), so an overflowing or near-zero float
+/// computation reports the mismatch instead of the def silently
+/// carrying a non-finite value forward.
+pub fn non_finite_error(op: &BinOp, left: &Expr, right: &Expr) -> Expr {
+    Expr::Error {
+        message: format!(
+            "{:?} between {:?} and {:?} produced a non-finite result (NaN/inf)",
+            op, left, right
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_widens_to_float() {
+        let coerced = coerce(&Expr::Number { val: 2 }, TargetKind::Float, false);
+        assert!(matches!(coerced, Some(Expr::Float { val }) if val == 2.0));
+    }
+
+    #[test]
+    fn float_to_float_passes_through() {
+        let coerced = coerce(&Expr::Float { val: 1.5 }, TargetKind::Float, false);
+        assert!(matches!(coerced, Some(Expr::Float { val }) if val == 1.5));
+    }
+
+    #[test]
+    fn int_to_bool_requires_opt_in() {
+        let expr = Expr::Number { val: 5 };
+        assert!(coerce(&expr, TargetKind::Boolean, false).is_none());
+        assert!(matches!(
+            coerce(&expr, TargetKind::Boolean, true),
+            Some(Expr::Bool { val: true })
+        ));
+    }
+
+    #[test]
+    fn zero_coerces_to_false_under_opt_in() {
+        let coerced = coerce(&Expr::Number { val: 0 }, TargetKind::Boolean, true);
+        assert!(matches!(coerced, Some(Expr::Bool { val: false })));
+    }
+
+    #[test]
+    fn no_coercion_path_returns_none() {
+        assert!(coerce(&Expr::Bool { val: true }, TargetKind::Bytes, false).is_none());
+    }
+
+    #[test]
+    fn negative_zero_equals_positive_zero() {
+        assert!(float_eq(0.0, -0.0));
+    }
+
+    #[test]
+    fn nan_is_never_equal_to_itself() {
+        assert!(!float_eq(f64::NAN, f64::NAN));
+    }
+}
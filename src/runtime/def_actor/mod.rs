@@ -1,7 +1,10 @@
 use futures::future::Either;
 use kameo::actor::ActorRef;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::task::Poll;
 
 use super::pubsub::PubSub;
 use crate::ast::Expr;
@@ -22,6 +25,35 @@ pub mod state;
 //             + Send + 'static,
 // >;
 
+/// Second, independent lane for the 128-bit fingerprints below.
+///
+/// Pairing `DefaultHasher` (SipHash) with a second `DefaultHasher` would
+/// only differ by whatever distinct bytes are fed in beforehand, leaving
+/// the two lanes highly correlated (same keyed algorithm, same key) and
+/// buying little over a single 64-bit hash. A small FNV-1a implementation
+/// gives the second lane a genuinely different algorithm instead.
+struct Fnv64Hasher(u64);
+
+impl Default for Fnv64Hasher {
+    fn default() -> Self {
+        Fnv64Hasher(0xcbf2_9ce4_8422_2325) // FNV-1a 64-bit offset basis
+    }
+}
+
+impl Hasher for Fnv64Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// A value with its BasisStamp (for basis checking)
 #[derive(Debug, Clone)]
 pub struct StampedValue {
@@ -51,6 +83,13 @@ pub struct DefActor {
     pub input_buffers: HashMap<String, Vec<StampedValue>>,
     pub current_inputs: HashMap<String, StampedValue>,
     pub current_basis: crate::runtime::message::BasisStamp,
+
+    // NEW: Fingerprints for recomputation memoization
+    pub last_input_fingerprint: Option<u128>,
+    pub last_output_fingerprint: Option<u128>,
+
+    // NEW: Explicit opt-in for the integer -> Bool coercion, 0 / non-zero (see conversion.rs)
+    pub allow_int_to_bool: bool,
 }
 
 impl DefActor {
@@ -81,6 +120,13 @@ impl DefActor {
             input_buffers: HashMap::new(),
             current_inputs: HashMap::new(),
             current_basis: crate::runtime::message::BasisStamp::empty(),
+
+            // NEW: No batch has been seen yet
+            last_input_fingerprint: None,
+            last_output_fingerprint: None,
+
+            // NEW: Off by default; see conversion::coerce
+            allow_int_to_bool: false,
         }
     }
     
@@ -115,7 +161,20 @@ impl DefActor {
         // Try to find a consistent batch
         if let Some(consistent_inputs) = self.find_consistent_batch(&input_names) {
             println!("[DefActor {}] Found consistent batch! Computing new value...", self.name);
-            
+
+            // Short-circuit if this batch is value-identical to the one
+            // already reflected in current_inputs, so an upstream var
+            // flapping between equal values doesn't cause a propagation
+            // storm.
+            let input_fingerprint = Self::fingerprint_batch(&consistent_inputs);
+            if self.last_input_fingerprint == Some(input_fingerprint) {
+                println!(
+                    "[DefActor {}] Batch fingerprint unchanged ({:x}), skipping recompute",
+                    self.name, input_fingerprint
+                );
+                return false;
+            }
+
             // Compute new value with these inputs
             if let Some(new_value) = self.evaluate_with_inputs(&consistent_inputs) {
                 // Merge all input bases to get output basis
@@ -123,15 +182,31 @@ impl DefActor {
                 for stamped_value in consistent_inputs.values() {
                     output_basis.merge_from(&stamped_value.basis);
                 }
-                
-                println!("[DefActor {}] New value computed: {:?}, basis: {:?}", 
+
+                // Because BasisStamp merges are associative, fingerprint the
+                // merged output too, so downstream defs can cheaply be told
+                // "unchanged" without re-deriving it from the inputs.
+                let output_fingerprint = Self::fingerprint_output(&new_value, &output_basis);
+                if self.last_output_fingerprint == Some(output_fingerprint) {
+                    println!(
+                        "[DefActor {}] Output fingerprint unchanged ({:x}), skipping publish",
+                        self.name, output_fingerprint
+                    );
+                    self.last_input_fingerprint = Some(input_fingerprint);
+                    self.current_inputs = consistent_inputs;
+                    return false;
+                }
+
+                println!("[DefActor {}] New value computed: {:?}, basis: {:?}",
                          self.name, new_value, output_basis);
-                
+
                 // Update current state
                 self.value = new_value.clone();
                 self.current_basis = output_basis.clone();
                 self.current_inputs = consistent_inputs;
-                
+                self.last_input_fingerprint = Some(input_fingerprint);
+                self.last_output_fingerprint = Some(output_fingerprint);
+
                 // Publish the new value
                 let msg = crate::runtime::message::Msg::PropChange {
                     from_name: self.name.clone(),
@@ -139,9 +214,9 @@ impl DefActor {
                     preds: HashSet::new(), // TODO: compute proper preds
                     basis: output_basis,
                 };
-                
+
                 self.pubsub.publish(msg).await;
-                
+
                 return true;
             }
         } else {
@@ -151,43 +226,206 @@ impl DefActor {
         false
     }
     
-    /// Find a consistent batch of inputs where all dependencies are satisfied
-    /// This is the core of the basis checking algorithm
+    /// Run `find_consistent_batch` once, synchronously, without awaiting
+    /// the actor mailbox or publishing via `pubsub`. Lets an embedder
+    /// driving its own event loop step the reactive graph deterministically,
+    /// one quiescent round at a time, instead of racing the async runtime.
+    ///
+    /// This updates `self.value`/`self.current_basis` (so the def's own
+    /// state reflects the new batch) but does *not* publish a `PropChange`
+    /// and does *not* write the result into any other def's
+    /// `input_buffers` — propagating it to downstream defs is the
+    /// caller's job, e.g. via `Manager::drain_ready`.
+    ///
+    /// Returns `Poll::Pending` when no complete batch exists yet,
+    /// `Poll::Ready(Some(_))` with the freshly computed value when one
+    /// was found, and `Poll::Ready(None)` when there was nothing to do
+    /// (no buffered updates, or the batch is fingerprint-identical to the
+    /// last one computed).
+    pub fn poll_for_consistent_batch(&mut self) -> Poll<Option<StampedValue>> {
+        let input_names = self.get_input_names();
+
+        let has_updates = input_names.iter()
+            .any(|name| self.input_buffers.get(name)
+                .map(|buf| !buf.is_empty())
+                .unwrap_or(false));
+
+        if !has_updates {
+            return Poll::Ready(None);
+        }
+
+        let consistent_inputs = match self.find_consistent_batch(&input_names) {
+            Some(inputs) => inputs,
+            None => return Poll::Pending,
+        };
+
+        let input_fingerprint = Self::fingerprint_batch(&consistent_inputs);
+        if self.last_input_fingerprint == Some(input_fingerprint) {
+            return Poll::Ready(None);
+        }
+
+        let new_value = match self.evaluate_with_inputs(&consistent_inputs) {
+            Some(value) => value,
+            None => return Poll::Pending,
+        };
+
+        let mut output_basis = crate::runtime::message::BasisStamp::empty();
+        for stamped_value in consistent_inputs.values() {
+            output_basis.merge_from(&stamped_value.basis);
+        }
+        let output_fingerprint = Self::fingerprint_output(&new_value, &output_basis);
+
+        self.value = new_value.clone();
+        self.current_basis = output_basis.clone();
+        self.current_inputs = consistent_inputs;
+        self.last_input_fingerprint = Some(input_fingerprint);
+        self.last_output_fingerprint = Some(output_fingerprint);
+
+        Poll::Ready(Some(StampedValue {
+            value: new_value,
+            basis: output_basis,
+        }))
+    }
+
+    /// Find a consistent batch of inputs where all dependencies are satisfied.
+    /// This is the core of the basis checking algorithm.
+    ///
+    /// A buffered update always wins over the value already reflected in
+    /// `current_inputs`: preferring `current_inputs` would mean that once
+    /// an input had been used once, every genuinely new value buffered
+    /// for it afterwards was silently ignored forever. The buffered entry
+    /// consumed into the batch is removed (FIFO, oldest first) so it
+    /// isn't folded in again on the next call and `input_buffers` doesn't
+    /// grow without bound.
     fn find_consistent_batch(
-        &self,
+        &mut self,
         input_names: &[String],
     ) -> Option<HashMap<String, StampedValue>> {
         let mut result = HashMap::new();
-        
-        // For each input, try to get a value
+
+        // Check every input has a value before consuming any buffered
+        // entry, so a batch that can't complete leaves the buffers intact.
         for input_name in input_names {
-            // First check if we have a current value
-            if let Some(current) = self.current_inputs.get(input_name) {
-                result.insert(input_name.clone(), current.clone());
+            let buffered = self.input_buffers.get(input_name).map_or(false, |buf| !buf.is_empty());
+            if !buffered && !self.current_inputs.contains_key(input_name) {
+                println!("[DefActor {}] No value available for input '{}'", self.name, input_name);
+                return None;
             }
-            // Then check buffered updates
-            else if let Some(buffer) = self.input_buffers.get(input_name) {
-                if let Some(first_update) = buffer.first() {
-                    result.insert(input_name.clone(), first_update.clone());
-                } else {
-                    println!("[DefActor {}] No value available for input '{}'", self.name, input_name);
-                    return None; // No value available for this input
+        }
+
+        for input_name in input_names {
+            if let Some(buffer) = self.input_buffers.get_mut(input_name) {
+                if !buffer.is_empty() {
+                    result.insert(input_name.clone(), buffer.remove(0));
+                    continue;
                 }
-            } else {
-                println!("[DefActor {}] No buffer for input '{}'", self.name, input_name);
-                return None; // No value available for this input
             }
+
+            if let Some(current) = self.current_inputs.get(input_name) {
+                result.insert(input_name.clone(), current.clone());
+                continue;
+            }
+
+            println!("[DefActor {}] No value available for input '{}'", self.name, input_name);
+            return None; // No value available for this input
         }
-        
+
         // Verify we have all required inputs
         if result.len() != input_names.len() {
             return None;
         }
-        
+
         println!("[DefActor {}] Consistent batch found with {} inputs", self.name, result.len());
         Some(result)
     }
     
+    /// Hash an `Expr` by its structural content, field by field, rather
+    /// than via `Expr: Hash`. `Expr::Float` carries a bare `f64`, which
+    /// can't be part of a `#[derive(Hash)]` (`f64` doesn't implement
+    /// `Hash`), so the fingerprinting below can't assume a derived impl
+    /// exists on `Expr` and instead folds each variant's fields in by
+    /// hand, converting a `Float`'s payload through `to_bits()`.
+    fn hash_expr<H: Hasher>(expr: &Expr, state: &mut H) {
+        match expr {
+            Expr::Number { val } => {
+                0u8.hash(state);
+                val.hash(state);
+            }
+            Expr::Bool { val } => {
+                1u8.hash(state);
+                val.hash(state);
+            }
+            Expr::Float { val } => {
+                2u8.hash(state);
+                val.to_bits().hash(state);
+            }
+            Expr::Variable { ident } => {
+                3u8.hash(state);
+                ident.hash(state);
+            }
+            Expr::Binop { op, expr1, expr2 } => {
+                4u8.hash(state);
+                op.hash(state);
+                Self::hash_expr(expr1, state);
+                Self::hash_expr(expr2, state);
+            }
+            Expr::Unop { op, expr } => {
+                5u8.hash(state);
+                op.hash(state);
+                Self::hash_expr(expr, state);
+            }
+            Expr::Error { message } => {
+                6u8.hash(state);
+                message.hash(state);
+            }
+        }
+    }
+
+    /// Compute a 128-bit content fingerprint over the sorted
+    /// `(name, value, basis)` tuples of a consistent batch, so two batches
+    /// that are value-identical hash equal regardless of arrival order.
+    ///
+    /// Hashes `value` through [`Self::hash_expr`] and `basis` through its
+    /// `Hash` impl rather than `{:?}`-formatting them first: `BasisStamp`
+    /// is map-backed, so its `Debug` iteration order isn't guaranteed
+    /// stable, and two logically-equal bases could otherwise stringify
+    /// (and therefore fingerprint) differently.
+    fn fingerprint_batch(batch: &HashMap<String, StampedValue>) -> u128 {
+        let mut entries: Vec<&String> = batch.keys().collect();
+        entries.sort();
+
+        let mut lo = DefaultHasher::new();
+        let mut hi = Fnv64Hasher::default();
+
+        for name in entries {
+            let stamped = &batch[name];
+            name.hash(&mut lo);
+            Self::hash_expr(&stamped.value, &mut lo);
+            stamped.basis.hash(&mut lo);
+
+            name.hash(&mut hi);
+            Self::hash_expr(&stamped.value, &mut hi);
+            stamped.basis.hash(&mut hi);
+        }
+
+        ((hi.finish() as u128) << 64) | (lo.finish() as u128)
+    }
+
+    /// Fingerprint a def's merged output (value + basis) the same way, so
+    /// downstream defs can compare against the last published output
+    /// cheaply instead of re-deriving it from the inputs.
+    fn fingerprint_output(value: &Expr, basis: &crate::runtime::message::BasisStamp) -> u128 {
+        let mut lo = DefaultHasher::new();
+        let mut hi = Fnv64Hasher::default();
+
+        Self::hash_expr(value, &mut lo);
+        basis.hash(&mut lo);
+        Self::hash_expr(value, &mut hi);
+        basis.hash(&mut hi);
+
+        ((hi.finish() as u128) << 64) | (lo.finish() as u128)
+    }
+
     /// Evaluate expression with given input values
     fn evaluate_with_inputs(
         &self,
@@ -213,8 +451,15 @@ impl DefActor {
             Expr::Binop { op, expr1, expr2 } => {
                 let left_val = self.substitute_expr(expr1, subst);
                 let right_val = self.substitute_expr(expr2, subst);
-                
-                // Try to evaluate if both are constants
+
+                // Coerce partially-evaluated subtrees to a common type
+                // up front, so a mismatched literal pair (e.g. a Number
+                // compared against a Bool) is resolved consistently here
+                // rather than relying on eval_binop's own fallback arm.
+                let (left_val, right_val) =
+                    Self::coerce_operands(op, &left_val, &right_val, self.allow_int_to_bool)
+                        .unwrap_or((left_val, right_val));
+
                 self.eval_binop(op, &left_val, &right_val)
             }
             Expr::Unop { op, expr: operand } => {
@@ -224,12 +469,24 @@ impl DefActor {
             _ => expr.clone(),
         }
     }
-    
+
     /// Evaluate a binary operation
     fn eval_binop(&self, op: &crate::ast::BinOp, left: &Expr, right: &Expr) -> Expr {
+        Self::eval_binop_with_policy(op, left, right, self.allow_int_to_bool)
+    }
+
+    /// Pure binop evaluator taking the coercion policy explicitly (rather
+    /// than reading `self.allow_int_to_bool`), so it's unit-testable
+    /// without constructing a `DefActor`/actor system.
+    fn eval_binop_with_policy(
+        op: &crate::ast::BinOp,
+        left: &Expr,
+        right: &Expr,
+        allow_int_to_bool: bool,
+    ) -> Expr {
         use crate::ast::BinOp::*;
         use Expr::*;
-        
+
         match (left, right) {
             (Number { val: l }, Number { val: r }) => {
                 match op {
@@ -240,10 +497,10 @@ impl DefActor {
                     Eq => Bool { val: l == r },
                     Lt => Bool { val: l < r },
                     Gt => Bool { val: l > r },
-                    _ => Binop { 
-                        op: op.clone(), 
-                        expr1: Box::new(left.clone()), 
-                        expr2: Box::new(right.clone()) 
+                    _ => Binop {
+                        op: op.clone(),
+                        expr1: Box::new(left.clone()),
+                        expr2: Box::new(right.clone())
                     },
                 }
             }
@@ -252,18 +509,124 @@ impl DefActor {
                     And => Bool { val: *l && *r },
                     Or => Bool { val: *l || *r },
                     Eq => Bool { val: l == r },
-                    _ => Binop { 
-                        op: op.clone(), 
-                        expr1: Box::new(left.clone()), 
-                        expr2: Box::new(right.clone()) 
+                    _ => Binop {
+                        op: op.clone(),
+                        expr1: Box::new(left.clone()),
+                        expr2: Box::new(right.clone())
                     },
                 }
             }
-            _ => Binop { 
-                op: op.clone(), 
-                expr1: Box::new(left.clone()), 
-                expr2: Box::new(right.clone()) 
+            (Float { val: l }, Float { val: r }) => match op {
+                // Arithmetic: compute then check the result is finite,
+                // rather than letting an overflow/div-by-near-zero carry
+                // a NaN/inf forward as a value the def would never be
+                // able to react consistently to.
+                Add | Sub | Mul | Div => {
+                    let result = match op {
+                        Add => Some(l + r),
+                        Sub => Some(l - r),
+                        Mul => Some(l * r),
+                        Div if *r != 0.0 => Some(l / r),
+                        _ => None, // Div by exactly zero: leave unevaluated.
+                    };
+                    match result {
+                        Some(v) if v.is_finite() => Float { val: v },
+                        Some(_) => crate::runtime::conversion::non_finite_error(op, left, right),
+                        None => Binop {
+                            op: op.clone(),
+                            expr1: Box::new(left.clone()),
+                            expr2: Box::new(right.clone()),
+                        },
+                    }
+                }
+                // Plain IEEE-754 equality via `conversion::float_eq`, so
+                // `-0.0 == 0.0` and `NaN == NaN` behave the way every
+                // other float comparison in the system does, instead of
+                // the bit-pattern equality an earlier revision used here
+                // (which flipped both of those cases).
+                Eq => Bool { val: crate::runtime::conversion::float_eq(*l, *r) },
+                Lt => Bool { val: l < r },
+                Gt => Bool { val: l > r },
+                _ => Binop {
+                    op: op.clone(),
+                    expr1: Box::new(left.clone()),
+                    expr2: Box::new(right.clone())
+                },
             },
+            // Operand kinds differ (e.g. `1 == true`, or a Number next to
+            // a Float): try a principled coercion before giving up,
+            // rather than silently leaving an unevaluated Binop for a def
+            // that can never become consistent.
+            _ => match Self::coerce_operands(op, left, right, allow_int_to_bool) {
+                Some((coerced_left, coerced_right)) => {
+                    Self::eval_binop_with_policy(op, &coerced_left, &coerced_right, allow_int_to_bool)
+                }
+                // Only report a type error once both sides are literals
+                // that genuinely can't be reconciled (e.g. `1 == true`
+                // without opt-in). A symbolic operand (`Variable`, an
+                // un-reduced `Binop`) hasn't finished reducing yet, so
+                // leaving it as an unevaluated Binop lets the def retry
+                // once its inputs settle instead of being permanently
+                // poisoned into an Error.
+                None if Self::is_concrete_literal(left) && Self::is_concrete_literal(right) => {
+                    crate::runtime::conversion::coercion_error(op, left, right)
+                }
+                None => Binop {
+                    op: op.clone(),
+                    expr1: Box::new(left.clone()),
+                    expr2: Box::new(right.clone()),
+                },
+            },
+        }
+    }
+
+    /// Whether `expr` is a fully-reduced literal `eval_binop_with_policy`
+    /// can act on directly (as opposed to a `Variable` or an un-reduced
+    /// `Binop`/`Unop` subtree still waiting on its own inputs).
+    fn is_concrete_literal(expr: &Expr) -> bool {
+        matches!(expr, Expr::Number { .. } | Expr::Bool { .. } | Expr::Float { .. })
+    }
+
+    /// Try to bring mismatched operands to a common type so `eval_binop`
+    /// can retry. Returns `None` (rather than recursing) when no coercion
+    /// applies; `eval_binop_with_policy` then yields a typed error when
+    /// both operands are literals and an unevaluated `Binop` otherwise,
+    /// and `substitute_expr` keeps the original operands.
+    fn coerce_operands(
+        op: &crate::ast::BinOp,
+        left: &Expr,
+        right: &Expr,
+        allow_int_to_bool: bool,
+    ) -> Option<(Expr, Expr)> {
+        use crate::runtime::conversion::{coerce, TargetKind};
+
+        match (left, right) {
+            (Expr::Number { .. }, Expr::Bool { .. }) => {
+                let coerced = coerce(left, TargetKind::Boolean, allow_int_to_bool)?;
+                Some((coerced, right.clone()))
+            }
+            (Expr::Bool { .. }, Expr::Number { .. }) => {
+                let coerced = coerce(right, TargetKind::Boolean, allow_int_to_bool)?;
+                Some((left.clone(), coerced))
+            }
+            // Promote the integer side to a float unconditionally: unlike
+            // the int->bool coercion, widening a Number into a Float never
+            // changes which branch of a comparison/arithmetic op fires.
+            (Expr::Number { .. }, Expr::Float { .. }) => {
+                let coerced = coerce(left, TargetKind::Float, allow_int_to_bool)?;
+                Some((coerced, right.clone()))
+            }
+            (Expr::Float { .. }, Expr::Number { .. }) => {
+                let coerced = coerce(right, TargetKind::Float, allow_int_to_bool)?;
+                Some((left.clone(), coerced))
+            }
+            _ => {
+                // Neither side has a coercion path yet (e.g. Bytes/Timestamp
+                // literals aren't representable in the AST today); let op
+                // stay unused here until those TargetKinds do.
+                let _ = op;
+                None
+            }
         }
     }
     
@@ -289,3 +652,130 @@ impl DefActor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::BinOp;
+
+    #[test]
+    fn adds_two_numbers() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Add,
+            &Expr::Number { val: 2 },
+            &Expr::Number { val: 3 },
+            false,
+        );
+        assert!(matches!(result, Expr::Number { val: 5 }));
+    }
+
+    #[test]
+    fn mixed_number_bool_eq_is_an_error_without_opt_in() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Eq,
+            &Expr::Number { val: 1 },
+            &Expr::Bool { val: true },
+            false,
+        );
+        assert!(matches!(result, Expr::Error { .. }));
+    }
+
+    #[test]
+    fn mixed_number_bool_eq_coerces_under_opt_in() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Eq,
+            &Expr::Number { val: 1 },
+            &Expr::Bool { val: true },
+            true,
+        );
+        assert!(matches!(result, Expr::Bool { val: true }));
+    }
+
+    #[test]
+    fn mismatched_symbolic_operand_stays_unevaluated() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Eq,
+            &Expr::Number { val: 1 },
+            &Expr::Variable { ident: "x".to_string() },
+            false,
+        );
+        assert!(matches!(result, Expr::Binop { .. }));
+    }
+
+    #[test]
+    fn number_promotes_to_float_before_comparison() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Lt,
+            &Expr::Number { val: 1 },
+            &Expr::Float { val: 1.5 },
+            false,
+        );
+        assert!(matches!(result, Expr::Bool { val: true }));
+    }
+
+    #[test]
+    fn float_overflow_is_a_typed_error_not_inf() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Mul,
+            &Expr::Float { val: f64::MAX },
+            &Expr::Float { val: f64::MAX },
+            false,
+        );
+        assert!(matches!(result, Expr::Error { .. }));
+    }
+
+    #[test]
+    fn float_division_by_zero_is_left_unevaluated() {
+        let result = DefActor::eval_binop_with_policy(
+            &BinOp::Div,
+            &Expr::Float { val: 1.0 },
+            &Expr::Float { val: 0.0 },
+            false,
+        );
+        assert!(matches!(result, Expr::Binop { .. }));
+    }
+
+    fn stamped(value: Expr) -> StampedValue {
+        StampedValue {
+            value,
+            basis: crate::runtime::message::BasisStamp::empty(),
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_order_independent() {
+        let mut a = HashMap::new();
+        a.insert("x".to_string(), stamped(Expr::Number { val: 1 }));
+        a.insert("y".to_string(), stamped(Expr::Number { val: 2 }));
+
+        let mut b = HashMap::new();
+        b.insert("y".to_string(), stamped(Expr::Number { val: 2 }));
+        b.insert("x".to_string(), stamped(Expr::Number { val: 1 }));
+
+        assert_eq!(DefActor::fingerprint_batch(&a), DefActor::fingerprint_batch(&b));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_value() {
+        let mut a = HashMap::new();
+        a.insert("x".to_string(), stamped(Expr::Number { val: 1 }));
+
+        let mut b = HashMap::new();
+        b.insert("x".to_string(), stamped(Expr::Number { val: 2 }));
+
+        assert_ne!(DefActor::fingerprint_batch(&a), DefActor::fingerprint_batch(&b));
+    }
+
+    #[test]
+    fn fingerprint_distinguishes_float_values() {
+        // Exercises the Expr::Float arm of hash_expr, which can't rely on
+        // a derived Hash impl since f64 isn't Hash.
+        let mut a = HashMap::new();
+        a.insert("x".to_string(), stamped(Expr::Float { val: 1.0 }));
+
+        let mut b = HashMap::new();
+        b.insert("x".to_string(), stamped(Expr::Float { val: 2.0 }));
+
+        assert_ne!(DefActor::fingerprint_batch(&a), DefActor::fingerprint_batch(&b));
+    }
+}
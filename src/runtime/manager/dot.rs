@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use super::Manager;
+
+impl Manager {
+    /// Render the reactive dependency graph as Graphviz DOT.
+    ///
+    /// Every def is a node, and every variable it transitively depends on
+    /// (per `dep_tran_vars`) is a node too, with an edge from the var to
+    /// the def that reads it. Glitch-free defs get a filled node style so
+    /// the stronger consistency guarantee is visible at a glance, which
+    /// is useful for debugging why `compute_affected_glitchfree` marks a
+    /// given def as affected.
+    pub fn to_dot(&self) -> String {
+        let mut nodes = HashSet::new();
+        for (def_name, vars) in &self.dep_tran_vars {
+            nodes.insert(def_name.clone());
+            nodes.extend(vars.iter().cloned());
+        }
+        // Sort before emitting: `nodes` is a HashSet and `dep_tran_vars`'s
+        // iteration order is unspecified, so without this the DOT output
+        // would vary run to run and couldn't be diffed or asserted on.
+        let mut nodes: Vec<String> = nodes.into_iter().collect();
+        nodes.sort();
+
+        let mut edges: Vec<(String, String)> = Vec::new();
+        for (def_name, vars) in &self.dep_tran_vars {
+            for var in vars {
+                edges.push((var.clone(), def_name.clone()));
+            }
+        }
+        edges.sort();
+
+        let mut dot = String::new();
+        dot.push_str("digraph reactive_graph {\n");
+
+        for node in &nodes {
+            if self.glitchfree_defs.contains(node) {
+                dot.push_str(&format!("    \"{}\" [style=filled];\n", node));
+            } else {
+                dot.push_str(&format!("    \"{}\";\n", node));
+            }
+        }
+
+        for (var, def_name) in &edges {
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n", var, def_name));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
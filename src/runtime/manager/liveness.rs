@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Manager;
+use crate::runtime::def_actor::DefActor;
+use crate::runtime::message::Msg;
+
+/// Pure core of [`Manager::compute_live_defs`], taking the transitive
+/// dependency map explicitly rather than through `self`, so it's
+/// unit-testable without constructing a `Manager`.
+///
+/// Classic worklist liveness pass: seed the worklist with `roots`, then
+/// repeatedly pop a def and add every def it reads from. Any def never
+/// reached is dead.
+fn compute_live_defs_from(
+    dep_tran_vars: &HashMap<String, HashSet<String>>,
+    roots: &HashSet<String>,
+) -> HashSet<String> {
+    let mut live: HashSet<String> = HashSet::new();
+    let mut worklist: Vec<String> = roots.iter().cloned().collect();
+
+    while let Some(def_name) = worklist.pop() {
+        if !live.insert(def_name.clone()) {
+            continue; // already visited
+        }
+
+        if let Some(read_vars) = dep_tran_vars.get(&def_name) {
+            for var in read_vars {
+                // Only defs (i.e. other keys of dep_tran_vars) need to be
+                // kept alive; plain input variables have no DefActor.
+                if dep_tran_vars.contains_key(var) && !live.contains(var) {
+                    worklist.push(var.clone());
+                }
+            }
+        }
+    }
+
+    live
+}
+
+/// Derive liveness roots from a caller-owned snapshot of `DefActor`s:
+/// every def with an active reader (`read_requests` or `test_read_request`)
+/// is a root. An assertion def counts as a root only while its
+/// `test_read_request` is pending: `DefActor` carries no other marker of
+/// being an assertion def, so one without a pending request is not a root.
+///
+/// `Manager::def_actors` holds `ActorRef<DefActor>`s reachable only
+/// through the actor mailbox (see `manager/poll.rs` for the same
+/// constraint), so this synchronous, owned-`DefActor` form is for
+/// callers driving a synchronous/test harness; a live, actor-backed
+/// `Manager` should use [`Manager::live_read_roots`] instead, which asks
+/// the same question over the mailbox.
+pub fn compute_roots(defs: &HashMap<String, DefActor>) -> HashSet<String> {
+    defs.iter()
+        .filter(|(_, def_actor)| {
+            !def_actor.read_requests.is_empty() || def_actor.test_read_request.is_some()
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+impl Manager {
+    /// Compute the set of defs that are still live, i.e. transitively
+    /// reachable backward from `roots` through `dep_tran_vars`.
+    ///
+    /// `roots` must already contain the active `read_requests`/
+    /// `test_read_request` consumers and any assertion defs — see
+    /// [`compute_roots`] for deriving the reader roots from an owned
+    /// `DefActor` snapshot.
+    pub fn compute_live_defs(&self, roots: &HashSet<String>) -> HashSet<String> {
+        compute_live_defs_from(&self.dep_tran_vars, roots)
+    }
+
+    /// Ask every live `DefActor` over its mailbox whether it currently has
+    /// an active reader (`read_requests` or `test_read_request`), the same
+    /// root condition [`compute_roots`] checks synchronously against an
+    /// owned `DefActor` snapshot. This is the path a real, actor-backed
+    /// `Manager` uses, since `self.def_actors` only ever holds
+    /// `ActorRef<DefActor>`, never a `DefActor` it could inspect directly.
+    ///
+    /// Requires a `Msg::HasActiveReader -> bool` variant and matching
+    /// `DefActor` handler; those live in `message.rs`/`def_actor::handler`
+    /// respectively, the same pre-existing gap noted on
+    /// [`Manager::read_and_confirm`] in `sync_read.rs` — neither file is
+    /// part of this tree, so this is wired against the message type this
+    /// variant is expected to live alongside rather than one defined here.
+    pub async fn live_read_roots(&self) -> HashSet<String> {
+        let mut roots = HashSet::new();
+
+        for (name, actor_ref) in &self.def_actors {
+            match actor_ref.ask(Msg::HasActiveReader).await {
+                Ok(true) => {
+                    roots.insert(name.clone());
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    println!(
+                        "[DEBUG] live_read_roots: ask failed for def '{}': {:?}",
+                        name, err
+                    );
+                }
+            }
+        }
+
+        roots
+    }
+
+    /// Shut down every `DefActor` not reachable from `roots`, freeing its
+    /// `input_buffers`, `buffered_outputs`, and `pubsub` subscriptions.
+    ///
+    /// `roots` is caller-supplied; see [`compute_roots`] for deriving it
+    /// from an owned `DefActor` snapshot, or [`Self::gc_dead_defs_live`]
+    /// to derive and collect in one call against a live `Manager`.
+    ///
+    /// Returns the names of the defs that were collected, so callers and
+    /// tests can assert on what got reclaimed.
+    pub async fn gc_dead_defs(&mut self, roots: &HashSet<String>) -> HashSet<String> {
+        let live = self.compute_live_defs(roots);
+
+        let dead: Vec<String> = self
+            .def_actors
+            .keys()
+            .filter(|def_name| !live.contains(*def_name))
+            .cloned()
+            .collect();
+
+        for def_name in &dead {
+            if let Some(actor_ref) = self.def_actors.remove(def_name) {
+                println!("[DEBUG] GC: stopping dead DefActor '{}'", def_name);
+                let _ = actor_ref.stop_gracefully().await;
+            }
+        }
+
+        dead.into_iter().collect()
+    }
+
+    /// Derive roots from the live actors via [`Self::live_read_roots`] and
+    /// collect everything unreachable from them in one call — the
+    /// actor-backed counterpart to calling [`compute_roots`] followed by
+    /// [`Self::gc_dead_defs`] against an owned `DefActor` snapshot.
+    pub async fn gc_dead_defs_live(&mut self) -> HashSet<String> {
+        let roots = self.live_read_roots().await;
+        self.gc_dead_defs(&roots).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep_map(pairs: &[(&str, &[&str])]) -> HashMap<String, HashSet<String>> {
+        pairs
+            .iter()
+            .map(|(def_name, vars)| {
+                (
+                    def_name.to_string(),
+                    vars.iter().map(|v| v.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn set(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn root_with_no_deps_is_live_alone() {
+        let deps = dep_map(&[("a", &["x"])]);
+        let live = compute_live_defs_from(&deps, &set(&["a"]));
+        assert_eq!(live, set(&["a"]));
+    }
+
+    #[test]
+    fn live_set_includes_transitive_def_dependencies() {
+        // c reads b, b reads a; only c is a root.
+        let deps = dep_map(&[("c", &["b"]), ("b", &["a"]), ("a", &["x"])]);
+        let live = compute_live_defs_from(&deps, &set(&["c"]));
+        assert_eq!(live, set(&["c", "b", "a"]));
+    }
+
+    #[test]
+    fn defs_unreachable_from_roots_are_dead() {
+        let deps = dep_map(&[("c", &["b"]), ("b", &["a"]), ("shadowed", &["a"])]);
+        let live = compute_live_defs_from(&deps, &set(&["c"]));
+        assert!(!live.contains("shadowed"));
+    }
+}
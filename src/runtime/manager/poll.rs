@@ -0,0 +1,77 @@
+use std::collections::{HashMap, HashSet};
+use std::task::Poll;
+
+use super::Manager;
+use crate::runtime::def_actor::DefActor;
+
+impl Manager {
+    /// Poll every def with a non-empty input buffer until a fixpoint is
+    /// reached, i.e. until a full pass produces no new values.
+    ///
+    /// This is the synchronous counterpart to the actor runtime: it steps
+    /// `defs` via [`DefActor::poll_for_consistent_batch`] so tests and host
+    /// applications can drive propagation one quiescent round at a time,
+    /// instead of racing the actor mailbox.
+    ///
+    /// `defs` is a caller-owned snapshot of `DefActor`s, not
+    /// `self.def_actors` — `Manager::def_actors` holds `ActorRef<DefActor>`s
+    /// reachable only by async message send, while this needs synchronous
+    /// `&mut DefActor` access to poll and to write propagated values
+    /// directly into downstream `input_buffers`. `self` is still used: the
+    /// transitive dependency map (`self.dep_tran_vars`, the same one
+    /// `compute_affected_glitchfree`/`compute_live_defs` key off of) is
+    /// what tells this pass which other defs in `defs` read a given def's
+    /// output, so a value produced this round is visible to its consumers
+    /// before the next round of this same loop runs.
+    ///
+    /// Returns the names of the defs that produced a new value at least
+    /// once during the drain.
+    pub fn drain_ready(&self, defs: &mut HashMap<String, DefActor>) -> HashSet<String> {
+        let mut produced = HashSet::new();
+
+        loop {
+            let mut ready = Vec::new();
+
+            for (name, def_actor) in defs.iter_mut() {
+                let has_updates = def_actor
+                    .input_buffers
+                    .values()
+                    .any(|buf| !buf.is_empty());
+                if !has_updates {
+                    continue;
+                }
+
+                if let Poll::Ready(Some(stamped)) = def_actor.poll_for_consistent_batch() {
+                    ready.push((name.clone(), stamped));
+                }
+            }
+
+            if ready.is_empty() {
+                break;
+            }
+
+            for (name, stamped) in ready {
+                produced.insert(name.clone());
+
+                // Route the new output into every downstream def's input
+                // buffer so it's visible on the next pass of this same
+                // loop, instead of silently stalling until some other
+                // caller re-feeds it in.
+                for (consumer, vars) in &self.dep_tran_vars {
+                    if consumer == &name || !vars.contains(&name) {
+                        continue;
+                    }
+                    if let Some(consumer_actor) = defs.get_mut(consumer) {
+                        consumer_actor
+                            .input_buffers
+                            .entry(name.clone())
+                            .or_default()
+                            .push(stamped.clone());
+                    }
+                }
+            }
+        }
+
+        produced
+    }
+}
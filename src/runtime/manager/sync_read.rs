@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use super::Manager;
+use crate::runtime::def_actor::StampedValue;
+use crate::runtime::message::Msg;
+use crate::runtime::transaction::Txn;
+
+/// Retry/timeout budget for [`Manager::read_and_confirm`].
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmBudget {
+    pub max_retries: usize,
+    pub retry_delay: Duration,
+}
+
+impl Default for ConfirmBudget {
+    fn default() -> Self {
+        ConfirmBudget {
+            max_retries: 10,
+            retry_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+impl Manager {
+    /// Register a read request for `def` on behalf of `txn` and return
+    /// immediately; the value arrives later via the usual `PropChange`
+    /// publication once it is causally settled.
+    pub async fn read(&mut self, def: &str, txn: &Txn) {
+        if let Some(actor_ref) = self.def_actors.get(def) {
+            let _ = actor_ref
+                .tell(Msg::ReadRequest {
+                    txn_id: txn.id,
+                    preds: txn.preds.clone(),
+                })
+                .send()
+                .await;
+        }
+    }
+
+    /// Block until `def`'s basis dominates `txn`'s predecessor set, i.e.
+    /// until the reader is guaranteed not to observe a glitched
+    /// intermediate value. Re-registers the read if the def republishes
+    /// with a newer basis before the budget is exhausted.
+    ///
+    /// Requires a `Msg::ReadAndConfirm { txn_id, preds } -> Option<StampedValue>`
+    /// variant and a matching `DefActor` message handler. Both belong in
+    /// `message.rs`/`def_actor::handler` respectively — files this chunk's
+    /// tree declares (`DefActor`'s `pub mod handler;`) but doesn't contain,
+    /// a pre-existing gap in this snapshot from before this request, same
+    /// as `message.rs` itself and `Manager::live_read_roots`'s
+    /// `Msg::HasActiveReader` in `liveness.rs`. This function is wired
+    /// against the message shape those files are expected to define, not
+    /// one fabricated here: adding the variant without the handler would
+    /// compile to a no-op that always returns `None`/times out, which is
+    /// a worse trap than the current honest "this crosses a file this
+    /// snapshot doesn't include" note.
+    pub async fn read_and_confirm(
+        &mut self,
+        def: &str,
+        txn: &Txn,
+        budget: ConfirmBudget,
+    ) -> Option<StampedValue> {
+        let actor_ref = self.def_actors.get(def)?.clone();
+
+        for attempt in 0..=budget.max_retries {
+            match actor_ref
+                .ask(Msg::ReadAndConfirm {
+                    txn_id: txn.id,
+                    preds: txn.preds.clone(),
+                })
+                .await
+            {
+                Ok(Some(stamped)) if stamped.basis.dominates(&txn.preds) => {
+                    return Some(stamped);
+                }
+                Ok(_) => {
+                    println!(
+                        "[DEBUG] read_and_confirm: def '{}' not yet settled for txn {:?} (attempt {}/{})",
+                        def, txn.id, attempt, budget.max_retries
+                    );
+                }
+                Err(err) => {
+                    println!(
+                        "[DEBUG] read_and_confirm: ask failed for def '{}': {:?}",
+                        def, err
+                    );
+                }
+            }
+
+            // Don't pay the delay after the last attempt — there's no
+            // further retry to space out, just the `None` return below.
+            if attempt < budget.max_retries {
+                tokio::time::sleep(budget.retry_delay).await;
+            }
+        }
+
+        None
+    }
+}